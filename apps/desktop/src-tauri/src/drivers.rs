@@ -0,0 +1,243 @@
+use std::path::PathBuf;
+
+use ffmpeg_sidecar::child::FfmpegChild;
+use ffmpeg_sidecar::command::FfmpegCommand;
+use serde::{Deserialize, Serialize};
+
+/// Paths to the media files a driver produced once a recording is stopped.
+pub struct RecordingOutput {
+    pub video_path: PathBuf,
+    pub audio_path: PathBuf,
+}
+
+pub struct StartOpts {
+    pub data_dir: Option<PathBuf>,
+    /// The avfoundation `<video>:<audio>` device string identifying which screen to
+    /// capture, e.g. `"1:0"` for the first screen device.
+    pub device: String,
+    pub width: usize,
+    pub height: usize,
+}
+
+/// Capture/mux orchestration is abstracted behind this trait so the Tauri commands in
+/// `recording.rs` don't need to know whether they're driving ffmpeg or a platform-native
+/// capturer. Each implementation owns its own process/handle and is responsible for
+/// cleaning it up on `stop`.
+pub trait RecordDriver: Send {
+    fn start(&mut self, opts: StartOpts) -> Result<(), String>;
+    fn stop(&mut self) -> Result<RecordingOutput, String>;
+    fn is_recording(&self) -> bool;
+    /// Retargets an in-progress recording to a new capture device/geometry, e.g. when
+    /// follow-focus mode switches to a different monitor. Implementations that can't
+    /// retarget in place may restart capture under the hood.
+    fn retarget(&mut self, device: &str, width: usize, height: usize) -> Result<(), String>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DriverKind {
+    Ffmpeg,
+    ScreenCaptureKit,
+    DesktopDuplication,
+}
+
+impl Default for DriverKind {
+    fn default() -> Self {
+        DriverKind::Ffmpeg
+    }
+}
+
+pub fn driver_for_kind(kind: DriverKind) -> Box<dyn RecordDriver> {
+    match kind {
+        DriverKind::Ffmpeg => Box::new(FfmpegDriver::default()),
+        DriverKind::ScreenCaptureKit => Box::new(UnsupportedDriver::new("ScreenCaptureKit")),
+        DriverKind::DesktopDuplication => Box::new(UnsupportedDriver::new("Desktop Duplication")),
+    }
+}
+
+#[derive(Default)]
+pub struct FfmpegDriver {
+    child: Option<FfmpegChild>,
+    base_dir: Option<PathBuf>,
+    audio_path: Option<PathBuf>,
+    /// One entry per `start`/`retarget` cycle; stitched together into the final
+    /// `video.mp4` on `stop` so switching monitors mid-recording doesn't discard
+    /// whatever was already captured.
+    video_segments: Vec<PathBuf>,
+}
+
+impl RecordDriver for FfmpegDriver {
+    fn start(&mut self, opts: StartOpts) -> Result<(), String> {
+        if self.child.is_some() {
+            return Err("ffmpeg driver is already recording".to_string());
+        }
+
+        let base = opts.data_dir.unwrap_or_else(PathBuf::new).join("content");
+        std::fs::create_dir_all(&base).map_err(|e| e.to_string())?;
+
+        let segment_path = base.join(format!("video_{}.mp4", self.video_segments.len()));
+
+        let child = FfmpegCommand::new()
+            .args(["-f", "avfoundation", "-i", &opts.device])
+            .args(["-s", &format!("{}x{}", opts.width, opts.height)])
+            .output(segment_path.to_string_lossy().to_string())
+            .spawn()
+            .map_err(|e| e.to_string())?;
+
+        self.child = Some(child);
+        self.base_dir = Some(base.clone());
+        self.audio_path.get_or_insert_with(|| base.join("audio.mp3"));
+        self.video_segments.push(segment_path);
+
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<RecordingOutput, String> {
+        if let Some(mut child) = self.child.take() {
+            let _ = child.quit();
+            let _ = child.wait();
+        }
+
+        let base = self
+            .base_dir
+            .take()
+            .ok_or_else(|| "ffmpeg driver was not recording".to_string())?;
+        let audio_path = self
+            .audio_path
+            .take()
+            .ok_or_else(|| "ffmpeg driver was not recording".to_string())?;
+        let segments = std::mem::take(&mut self.video_segments);
+
+        let video_path = base.join("video.mp4");
+        concat_segments(&segments, &video_path)?;
+
+        Ok(RecordingOutput {
+            video_path,
+            audio_path,
+        })
+    }
+
+    fn is_recording(&self) -> bool {
+        self.child.is_some()
+    }
+
+    fn retarget(&mut self, device: &str, width: usize, height: usize) -> Result<(), String> {
+        let base = self
+            .base_dir
+            .clone()
+            .ok_or_else(|| "ffmpeg driver is not recording".to_string())?;
+        let session_dir = base
+            .parent()
+            .map(PathBuf::from)
+            .ok_or_else(|| "ffmpeg driver has no session directory".to_string())?;
+
+        if let Some(mut child) = self.child.take() {
+            let _ = child.quit();
+            let _ = child.wait();
+        }
+
+        self.start(StartOpts {
+            data_dir: Some(session_dir),
+            device: device.to_string(),
+            width,
+            height,
+        })
+    }
+}
+
+/// Stitches per-segment recordings (one per `start`/`retarget` cycle, all the same
+/// codec/geometry-compatible ffmpeg output) into a single file. With only one segment
+/// this is just a rename; with more it uses ffmpeg's concat demuxer with `-c copy` so no
+/// re-encoding happens.
+pub(crate) fn concat_segments(segments: &[PathBuf], output: &PathBuf) -> Result<(), String> {
+    match segments {
+        [] => Err("No recorded segments to finalize".to_string()),
+        [only] => std::fs::rename(only, output).map_err(|e| e.to_string()),
+        segments => {
+            let list_path = output.with_file_name("segments.txt");
+            let list_contents: String = segments
+                .iter()
+                .map(|path| format!("file '{}'\n", path.to_string_lossy()))
+                .collect();
+            std::fs::write(&list_path, list_contents).map_err(|e| e.to_string())?;
+
+            let status = std::process::Command::new("ffmpeg")
+                .args(["-y", "-f", "concat", "-safe", "0", "-i"])
+                .arg(&list_path)
+                .args(["-c", "copy"])
+                .arg(output)
+                .status()
+                .map_err(|e| e.to_string())?;
+
+            if !status.success() {
+                return Err("Failed to concatenate recording segments".to_string());
+            }
+
+            Ok(())
+        }
+    }
+}
+
+/// Placeholder for a platform-native driver that hasn't been wired up on this platform yet.
+/// Keeping it as a real `RecordDriver` (rather than special-casing `DriverKind` at the call
+/// site) means the command layer never has to know which backends are actually implemented.
+struct UnsupportedDriver {
+    name: &'static str,
+}
+
+impl UnsupportedDriver {
+    fn new(name: &'static str) -> Self {
+        Self { name }
+    }
+}
+
+impl RecordDriver for UnsupportedDriver {
+    fn start(&mut self, _opts: StartOpts) -> Result<(), String> {
+        Err(format!("{} recording backend is not yet available", self.name))
+    }
+
+    fn stop(&mut self) -> Result<RecordingOutput, String> {
+        Err(format!("{} recording backend is not yet available", self.name))
+    }
+
+    fn is_recording(&self) -> bool {
+        false
+    }
+
+    fn retarget(&mut self, _device: &str, _width: usize, _height: usize) -> Result<(), String> {
+        Err(format!("{} recording backend is not yet available", self.name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("cap_drivers_test_{}_{}", std::process::id(), name));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn concat_segments_rejects_empty_input() {
+        let dir = scratch_dir("empty");
+        let output = dir.join("video.mp4");
+
+        assert!(concat_segments(&[], &output).is_err());
+    }
+
+    #[test]
+    fn concat_segments_renames_a_single_segment() {
+        let dir = scratch_dir("single");
+        let segment = dir.join("video_0.mp4");
+        std::fs::write(&segment, b"fake mp4 bytes").unwrap();
+        let output = dir.join("video.mp4");
+
+        concat_segments(&[segment.clone()], &output).unwrap();
+
+        assert!(!segment.exists());
+        assert_eq!(std::fs::read(&output).unwrap(), b"fake mp4 bytes");
+    }
+}
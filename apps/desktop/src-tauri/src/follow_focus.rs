@@ -0,0 +1,200 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use tauri::{command, AppHandle, Manager, State};
+use tokio::sync::Mutex;
+
+use crate::recording::RecordingState;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+const DEBOUNCE_INTERVAL: Duration = Duration::from_millis(750);
+
+#[derive(Debug, Clone)]
+pub struct MonitorInfo {
+    pub index: usize,
+    pub x: i32,
+    pub y: i32,
+    pub width: usize,
+    pub height: usize,
+    /// The avfoundation `<video>:<audio>` device string that captures this monitor,
+    /// e.g. `"1:0"` for the first screen device. Needed so `retarget` actually switches
+    /// which display ffmpeg reads from instead of just resizing the same capture.
+    pub capture_device: String,
+}
+
+impl MonitorInfo {
+    fn contains(&self, x: i32, y: i32) -> bool {
+        x >= self.x
+            && x < self.x + self.width as i32
+            && y >= self.y
+            && y < self.y + self.height as i32
+    }
+}
+
+/// Tracks which monitors are eligible for follow-focus and the index last switched to, so
+/// the watcher can debounce rapid focus changes instead of thrashing the encoder.
+pub struct FollowFocusState {
+    pub monitors: Vec<MonitorInfo>,
+    pub blacklist: Mutex<Vec<usize>>,
+    pub active_monitor: Mutex<Option<usize>>,
+}
+
+impl FollowFocusState {
+    pub fn new(monitors: Vec<MonitorInfo>) -> Self {
+        Self {
+            monitors,
+            blacklist: Mutex::new(Vec::new()),
+            active_monitor: Mutex::new(None),
+        }
+    }
+}
+
+/// Returns the current cursor location in screen coordinates, used as a stand-in for
+/// "which monitor holds the focused window" since that's what's cheaply pollable
+/// cross-platform; the focused window is almost always under the cursor or was most
+/// recently.
+#[cfg(target_os = "macos")]
+fn cursor_position() -> (i32, i32) {
+    use core_graphics::event::CGEvent;
+    use core_graphics::event_source::{CGEventSourceStateID, CGEventSource};
+
+    CGEventSource::new(CGEventSourceStateID::CombinedSessionState)
+        .ok()
+        .and_then(|source| CGEvent::new(source).ok())
+        .map(|event| {
+            let point = event.location();
+            (point.x as i32, point.y as i32)
+        })
+        .unwrap_or((0, 0))
+}
+
+#[cfg(not(target_os = "macos"))]
+fn cursor_position() -> (i32, i32) {
+    (0, 0)
+}
+
+fn monitor_at(monitors: &[MonitorInfo], x: i32, y: i32) -> Option<&MonitorInfo> {
+    monitors.iter().find(|m| m.contains(x, y))
+}
+
+/// Records which monitor a follow-focus recording actually started on, so the watcher's
+/// first poll tick compares against where capture began instead of `None` — otherwise it
+/// always sees a "change" on the first tick and retargets immediately, restarting capture
+/// into a new segment at the start of every follow-focus recording.
+pub async fn seed_active_monitor(app: &AppHandle) {
+    let follow_focus_state: State<'_, FollowFocusState> = app.state();
+    let (x, y) = cursor_position();
+
+    let monitor_index = monitor_at(&follow_focus_state.monitors, x, y).map(|m| m.index);
+    *follow_focus_state.active_monitor.lock().await = monitor_index;
+}
+
+#[command]
+pub async fn set_follow_focus_blacklist(
+    follow_focus_state: State<'_, FollowFocusState>,
+    monitor_indices: Vec<usize>,
+) -> Result<(), String> {
+    *follow_focus_state.blacklist.lock().await = monitor_indices;
+    Ok(())
+}
+
+/// Spawned from `setup`; while a recording with `follow_focus` enabled is active, polls the
+/// cursor location and retargets the active `RecordDriver` whenever it lands on a different,
+/// non-blacklisted monitor than the one currently being captured.
+pub fn spawn_watcher(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut last_switch = tokio::time::Instant::now() - DEBOUNCE_INTERVAL;
+
+        loop {
+            tokio::time::sleep(POLL_INTERVAL).await;
+
+            let follow_focus_state: State<'_, FollowFocusState> = app.state();
+            let recording_state: State<'_, Arc<Mutex<RecordingState>>> = app.state();
+
+            let mut state_guard = recording_state.lock().await;
+
+            let follow_focus_enabled = state_guard
+                .recording_options
+                .as_ref()
+                .map(|o| o.follow_focus)
+                .unwrap_or(false);
+
+            if !follow_focus_enabled || state_guard.driver.is_none() {
+                continue;
+            }
+
+            if last_switch.elapsed() < DEBOUNCE_INTERVAL {
+                continue;
+            }
+
+            let (x, y) = cursor_position();
+            let blacklist = follow_focus_state.blacklist.lock().await;
+
+            let Some(monitor) = monitor_at(&follow_focus_state.monitors, x, y) else {
+                continue;
+            };
+
+            if blacklist.contains(&monitor.index) {
+                continue;
+            }
+            drop(blacklist);
+
+            let mut active_monitor = follow_focus_state.active_monitor.lock().await;
+            if *active_monitor == Some(monitor.index) {
+                continue;
+            }
+
+            if let Some(driver) = state_guard.driver.as_mut() {
+                match driver.retarget(&monitor.capture_device, monitor.width, monitor.height) {
+                    Ok(()) => {
+                        state_guard.max_screen_width = monitor.width;
+                        state_guard.max_screen_height = monitor.height;
+                        *active_monitor = Some(monitor.index);
+                        last_switch = tokio::time::Instant::now();
+                        let _ = app.emit_all("follow-focus-switched", monitor.index);
+                    }
+                    Err(err) => {
+                        eprintln!("Failed to retarget capture to monitor {}: {}", monitor.index, err);
+                    }
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monitor(index: usize, x: i32, y: i32, width: usize, height: usize) -> MonitorInfo {
+        MonitorInfo {
+            index,
+            x,
+            y,
+            width,
+            height,
+            capture_device: format!("{}:0", index + 1),
+        }
+    }
+
+    #[test]
+    fn contains_checks_monitor_bounds() {
+        let m = monitor(0, 0, 0, 1920, 1080);
+
+        assert!(m.contains(0, 0));
+        assert!(m.contains(1919, 1079));
+        assert!(!m.contains(1920, 0));
+        assert!(!m.contains(0, 1080));
+        assert!(!m.contains(-1, 0));
+    }
+
+    #[test]
+    fn monitor_at_finds_the_monitor_containing_a_point() {
+        let monitors = vec![monitor(0, 0, 0, 1920, 1080), monitor(1, 1920, 0, 1280, 720)];
+
+        assert_eq!(monitor_at(&monitors, 100, 100).map(|m| m.index), Some(0));
+        assert_eq!(monitor_at(&monitors, 2000, 100).map(|m| m.index), Some(1));
+        assert_eq!(monitor_at(&monitors, 5000, 5000).map(|m| m.index), None);
+    }
+}
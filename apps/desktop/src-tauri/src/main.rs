@@ -12,14 +12,23 @@ use tauri_plugin_positioner::{WindowExt, Position};
 use tauri_plugin_oauth::start;
 
 mod recording;
+mod drivers;
 mod upload;
 mod utils;
 mod media;
+mod shortcuts;
+mod stream;
+mod follow_focus;
+mod recovery;
 
 use recording::{RecordingState, start_dual_recording, stop_all_recordings};
 use upload::upload_file;
 use media::{enumerate_audio_devices};
-use utils::{has_screen_capture_access};
+use utils::{has_screen_capture_access, get_media_access_status};
+use shortcuts::{register_shortcut, unregister_shortcut, register_default_shortcut, ShortcutState};
+use stream::{start_live_stream, stop_live_stream};
+use follow_focus::{spawn_watcher, set_follow_focus_blacklist, FollowFocusState, MonitorInfo};
+use recovery::{list_recoverable_recordings, log_recoverable_sessions, recover_recording};
 
 use ffmpeg_sidecar::{
     command::ffmpeg_is_installed,
@@ -159,6 +168,25 @@ fn main() {
         (0, 0)
     };
 
+    let monitor_infos: Vec<MonitorInfo> = event_loop
+        .available_monitors()
+        .enumerate()
+        .map(|(index, monitor)| {
+            let position = monitor.position();
+            let size = monitor.size();
+            MonitorInfo {
+                index,
+                x: position.x,
+                y: position.y,
+                width: size.width as usize,
+                height: size.height as usize,
+                // avfoundation lists screen devices after capture devices, starting at
+                // index 1; the enumeration order here matches `available_monitors()`.
+                capture_device: format!("{}:0", index + 1),
+            }
+        })
+        .collect();
+
     tauri::Builder::default()
         .plugin(tauri_plugin_oauth::init())
         .plugin(tauri_plugin_positioner::init())
@@ -173,7 +201,7 @@ fn main() {
                 let rt = tokio::runtime::Runtime::new().unwrap();
 
                 let stop_if_recording = async {
-                    if state.lock().await.media_process.is_some() {
+                    if state.lock().await.driver.is_some() {
                         let _ = stop_all_recordings(state).await;
                     }
                 };
@@ -198,18 +226,27 @@ fn main() {
 
             let data_directory = handle.path_resolver().app_data_dir().unwrap_or_else(|| PathBuf::new());
             let recording_state = RecordingState {
-                media_process: None,
+                driver: None,
+                stream: None,
                 upload_handles: Mutex::new(vec![]),
                 recording_options: None,
                 shutdown_flag: Arc::new(AtomicBool::new(false)),
                 video_uploading_finished: Arc::new(AtomicBool::new(false)),
                 audio_uploading_finished: Arc::new(AtomicBool::new(false)),
-                data_dir: Some(data_directory),
+                data_dir: Some(data_directory.clone()),
                 max_screen_width: max_width as usize,
                 max_screen_height: max_height as usize,
+                current_session: None,
             };
 
+            log_recoverable_sessions(&data_directory);
+
             app.manage(Arc::new(Mutex::new(recording_state)));
+            app.manage(ShortcutState::new(Some(data_directory.clone())));
+            app.manage(FollowFocusState::new(monitor_infos));
+
+            register_default_shortcut(&handle, &Some(data_directory));
+            spawn_watcher(handle.clone());
 
             Ok(())
         })
@@ -223,9 +260,17 @@ fn main() {
             open_mic_preferences,
             open_camera_preferences,
             has_screen_capture_access,
+            get_media_access_status,
             reset_screen_permissions,
             reset_microphone_permissions,
             reset_camera_permissions,
+            register_shortcut,
+            unregister_shortcut,
+            start_live_stream,
+            stop_live_stream,
+            set_follow_focus_blacklist,
+            list_recoverable_recordings,
+            recover_recording,
         ])
         .plugin(tauri_plugin_context_menu::init())
         .run(tauri::generate_context!())
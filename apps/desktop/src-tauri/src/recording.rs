@@ -0,0 +1,185 @@
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tauri::{command, AppHandle, Manager, State};
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+
+use crate::drivers::{driver_for_kind, DriverKind, RecordDriver, StartOpts};
+use crate::follow_focus::FollowFocusState;
+use crate::recovery::{
+    mark_audio_uploaded, mark_stopped, mark_video_uploaded, session_dir, RecordingManifest,
+    SessionStatus,
+};
+use crate::stream::LiveStream;
+use crate::upload::upload_file;
+
+const DEFAULT_CAPTURE_DEVICE: &str = "1:0";
+
+/// Resolves the avfoundation device string for the screen a recording should start on:
+/// the requested `screen_index` if it names a known monitor, otherwise the first
+/// enumerated monitor, falling back to the historical default if none were found.
+fn capture_device_for(app: &AppHandle, screen_index: Option<usize>) -> String {
+    let follow_focus_state: State<'_, FollowFocusState> = app.state();
+
+    screen_index
+        .and_then(|index| {
+            follow_focus_state
+                .monitors
+                .iter()
+                .find(|m| m.index == index)
+        })
+        .or_else(|| follow_focus_state.monitors.first())
+        .map(|m| m.capture_device.clone())
+        .unwrap_or_else(|| DEFAULT_CAPTURE_DEVICE.to_string())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordingOptions {
+    pub user_id: String,
+    pub video_device_id: Option<String>,
+    pub audio_device_id: Option<String>,
+    pub screen_index: Option<usize>,
+    #[serde(default)]
+    pub driver: DriverKind,
+    #[serde(default)]
+    pub follow_focus: bool,
+}
+
+pub struct RecordingState {
+    pub driver: Option<Box<dyn RecordDriver>>,
+    pub stream: Option<LiveStream>,
+    pub upload_handles: Mutex<Vec<JoinHandle<()>>>,
+    pub recording_options: Option<RecordingOptions>,
+    pub shutdown_flag: Arc<AtomicBool>,
+    pub video_uploading_finished: Arc<AtomicBool>,
+    pub audio_uploading_finished: Arc<AtomicBool>,
+    pub data_dir: Option<PathBuf>,
+    pub max_screen_width: usize,
+    pub max_screen_height: usize,
+    pub current_session: Option<String>,
+}
+
+#[command]
+pub async fn start_dual_recording(
+    state: State<'_, Arc<Mutex<RecordingState>>>,
+    app: AppHandle,
+    options: Option<RecordingOptions>,
+) -> Result<(), String> {
+    let mut state_guard = state.lock().await;
+
+    if state_guard.driver.is_some() {
+        return Err("A recording is already in progress".to_string());
+    }
+
+    state_guard.shutdown_flag.store(false, Ordering::Relaxed);
+    state_guard.video_uploading_finished.store(false, Ordering::Relaxed);
+    state_guard.audio_uploading_finished.store(false, Ordering::Relaxed);
+
+    if let Some(options) = options {
+        state_guard.recording_options = Some(options);
+    }
+
+    let driver_kind = state_guard
+        .recording_options
+        .as_ref()
+        .map(|o| o.driver)
+        .unwrap_or_default();
+    let follow_focus_enabled = state_guard
+        .recording_options
+        .as_ref()
+        .map(|o| o.follow_focus)
+        .unwrap_or(false);
+    let screen_index = state_guard
+        .recording_options
+        .as_ref()
+        .and_then(|o| o.screen_index);
+
+    let data_dir = state_guard.data_dir.clone().unwrap_or_else(PathBuf::new);
+    let session_id = uuid::Uuid::new_v4().to_string();
+    let session_path = session_dir(&data_dir, &session_id);
+
+    let mut driver = driver_for_kind(driver_kind);
+    driver.start(StartOpts {
+        data_dir: Some(session_path.clone()),
+        device: capture_device_for(&app, screen_index),
+        width: state_guard.max_screen_width,
+        height: state_guard.max_screen_height,
+    })?;
+
+    crate::recovery::write_manifest(
+        &session_path,
+        &RecordingManifest {
+            session_id: session_id.clone(),
+            status: SessionStatus::InProgress,
+            video_uploaded: false,
+            audio_uploaded: false,
+        },
+    );
+
+    state_guard.driver = Some(driver);
+    state_guard.current_session = Some(session_id);
+
+    if follow_focus_enabled {
+        crate::follow_focus::seed_active_monitor(&app).await;
+    }
+
+    let _ = app.emit_all("recording-started", ());
+
+    Ok(())
+}
+
+#[command]
+pub async fn stop_all_recordings(state: State<'_, Arc<Mutex<RecordingState>>>) -> Result<(), String> {
+    let mut state_guard = state.lock().await;
+
+    state_guard.shutdown_flag.store(true, Ordering::Relaxed);
+
+    let mut driver = match state_guard.driver.take() {
+        Some(driver) => driver,
+        None => return Ok(()),
+    };
+    let output = driver.stop()?;
+
+    let data_dir = state_guard.data_dir.clone().unwrap_or_else(PathBuf::new);
+    let session_path = state_guard
+        .current_session
+        .take()
+        .map(|session_id| session_dir(&data_dir, &session_id));
+
+    if let Some(session_path) = &session_path {
+        mark_stopped(session_path);
+    }
+
+    let video = output.video_path.to_string_lossy().to_string();
+    let audio = output.audio_path.to_string_lossy().to_string();
+    let video_finished = state_guard.video_uploading_finished.clone();
+    let audio_finished = state_guard.audio_uploading_finished.clone();
+    let video_session_path = session_path.clone();
+    let audio_session_path = session_path.clone();
+
+    let video_handle = tokio::spawn(async move {
+        if upload_file(video).await.is_ok() {
+            if let Some(session_path) = &video_session_path {
+                mark_video_uploaded(session_path);
+            }
+        }
+        video_finished.store(true, Ordering::Relaxed);
+    });
+    let audio_handle = tokio::spawn(async move {
+        if upload_file(audio).await.is_ok() {
+            if let Some(session_path) = &audio_session_path {
+                mark_audio_uploaded(session_path);
+            }
+        }
+        audio_finished.store(true, Ordering::Relaxed);
+    });
+
+    let mut handles = state_guard.upload_handles.lock().await;
+    handles.push(video_handle);
+    handles.push(audio_handle);
+
+    Ok(())
+}
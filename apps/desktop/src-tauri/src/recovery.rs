@@ -0,0 +1,313 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tauri::{command, State};
+use tokio::sync::Mutex;
+
+use crate::drivers::concat_segments;
+use crate::recording::RecordingState;
+use crate::upload::upload_file;
+
+const RECORDINGS_DIR: &str = "recordings";
+const MANIFEST_FILE: &str = "manifest.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SessionStatus {
+    InProgress,
+    Stopped,
+    Finalized,
+}
+
+/// Tracks a recording session's on-disk state so a panic or hard quit doesn't strand
+/// segments that were never muxed or uploaded. Written alongside the session's media
+/// files in `<data_dir>/recordings/<session_id>/manifest.json`. Doesn't track the
+/// driver's segment list itself — that changes every retarget and is cheaper to read
+/// straight off disk (see `recover_recording`) than to keep in sync here.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordingManifest {
+    pub session_id: String,
+    pub status: SessionStatus,
+    pub video_uploaded: bool,
+    pub audio_uploaded: bool,
+}
+
+pub fn session_dir(data_dir: &Path, session_id: &str) -> PathBuf {
+    data_dir.join(RECORDINGS_DIR).join(session_id)
+}
+
+fn manifest_path(session_dir: &Path) -> PathBuf {
+    session_dir.join(MANIFEST_FILE)
+}
+
+/// Finds the raw `video_0.mp4`, `video_1.mp4`, ... segments `FfmpegDriver` leaves behind
+/// when a session never reaches a clean `stop()` (crash, hard kill). Unsorted — callers
+/// that need them in recording order must sort the result themselves.
+fn video_segments(content_dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(content_dir) else {
+        return Vec::new();
+    };
+
+    entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_prefix("video_"))
+                .and_then(|name| name.strip_suffix(".mp4"))
+                .map(|index| index.parse::<usize>().is_ok())
+                .unwrap_or(false)
+        })
+        .collect()
+}
+
+pub fn write_manifest(session_dir: &Path, manifest: &RecordingManifest) {
+    let _ = fs::create_dir_all(session_dir);
+    if let Ok(json) = serde_json::to_string_pretty(manifest) {
+        let _ = fs::write(manifest_path(session_dir), json);
+    }
+}
+
+pub fn read_manifest(session_dir: &Path) -> Option<RecordingManifest> {
+    fs::read_to_string(manifest_path(session_dir))
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+}
+
+fn update_manifest(session_dir: &Path, update: impl FnOnce(&mut RecordingManifest)) {
+    if let Some(mut manifest) = read_manifest(session_dir) {
+        update(&mut manifest);
+        write_manifest(session_dir, &manifest);
+    }
+}
+
+pub fn mark_video_uploaded(session_dir: &Path) {
+    update_manifest(session_dir, |manifest| {
+        manifest.video_uploaded = true;
+        if manifest.video_uploaded && manifest.audio_uploaded {
+            manifest.status = SessionStatus::Finalized;
+        }
+    });
+}
+
+pub fn mark_audio_uploaded(session_dir: &Path) {
+    update_manifest(session_dir, |manifest| {
+        manifest.audio_uploaded = true;
+        if manifest.video_uploaded && manifest.audio_uploaded {
+            manifest.status = SessionStatus::Finalized;
+        }
+    });
+}
+
+pub fn mark_stopped(session_dir: &Path) {
+    update_manifest(session_dir, |manifest| {
+        manifest.status = SessionStatus::Stopped;
+    });
+}
+
+fn list_recoverable(data_dir: &Path) -> Vec<RecordingManifest> {
+    let Ok(entries) = fs::read_dir(data_dir.join(RECORDINGS_DIR)) else {
+        return Vec::new();
+    };
+
+    entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| read_manifest(&entry.path()))
+        .filter(|manifest| manifest.status != SessionStatus::Finalized)
+        .collect()
+}
+
+/// Called from `setup`; recovery is always explicit via `recover_recording` so a
+/// half-finished session is never silently re-uploaded behind the user's back.
+pub fn log_recoverable_sessions(data_dir: &Path) {
+    let recoverable = list_recoverable(data_dir);
+    if !recoverable.is_empty() {
+        println!(
+            "Found {} recording session(s) that weren't cleanly finalized",
+            recoverable.len()
+        );
+    }
+}
+
+#[command]
+pub async fn list_recoverable_recordings(
+    state: State<'_, Arc<Mutex<RecordingState>>>,
+) -> Result<Vec<RecordingManifest>, String> {
+    let state_guard = state.lock().await;
+    let data_dir = state_guard
+        .data_dir
+        .clone()
+        .ok_or("No data directory configured")?;
+    let active_session = state_guard.current_session.clone();
+
+    Ok(list_recoverable(&data_dir)
+        .into_iter()
+        .filter(|manifest| Some(&manifest.session_id) != active_session.as_ref())
+        .collect())
+}
+
+#[command]
+pub async fn recover_recording(
+    state: State<'_, Arc<Mutex<RecordingState>>>,
+    session_id: String,
+) -> Result<(), String> {
+    let state_guard = state.lock().await;
+    let data_dir = state_guard
+        .data_dir
+        .clone()
+        .ok_or("No data directory configured")?;
+
+    if state_guard.current_session.as_deref() == Some(session_id.as_str()) {
+        return Err("Cannot recover a recording session that is still in progress".to_string());
+    }
+    drop(state_guard);
+
+    let dir = session_dir(&data_dir, &session_id);
+    let mut manifest = read_manifest(&dir).ok_or("No manifest found for this session")?;
+
+    let content_dir = dir.join("content");
+    let video_path = content_dir.join("video.mp4");
+    let audio_path = content_dir.join("audio.mp3");
+
+    // A session stopped cleanly already has the segments stitched into `video.mp4` by
+    // `FfmpegDriver::stop`. A session that crashed or was killed never ran `stop`, so
+    // only the raw per-segment files (`video_0.mp4`, `video_1.mp4`, ...) are on disk —
+    // glob and stitch those the same way `stop` would have.
+    let recovered_video = if video_path.exists() {
+        Some(video_path.clone())
+    } else {
+        let mut segments = video_segments(&content_dir);
+        match segments.len() {
+            0 => None,
+            _ => {
+                let stitched = content_dir.join("video_recovered.mp4");
+                segments.sort_by_key(|path| {
+                    path.file_stem()
+                        .and_then(|stem| stem.to_str())
+                        .and_then(|stem| stem.strip_prefix("video_"))
+                        .and_then(|index| index.parse::<usize>().ok())
+                });
+                concat_segments(&segments, &stitched).ok().map(|_| stitched)
+            }
+        }
+    };
+
+    if let Some(recovered_video) = recovered_video {
+        if !manifest.video_uploaded {
+            // `-err_detect ignore_err` lets ffmpeg salvage a stream that was cut off
+            // before a clean trailer/moov atom was ever written.
+            let remuxed = content_dir.join("video_remuxed.mp4");
+            let status = std::process::Command::new("ffmpeg")
+                .args(["-y", "-err_detect", "ignore_err", "-i"])
+                .arg(&recovered_video)
+                .args(["-c", "copy"])
+                .arg(&remuxed)
+                .status();
+
+            if matches!(status, Ok(status) if status.success()) {
+                if upload_file(remuxed.to_string_lossy().to_string())
+                    .await
+                    .is_ok()
+                {
+                    manifest.video_uploaded = true;
+                }
+            }
+        }
+    }
+
+    if audio_path.exists() && !manifest.audio_uploaded {
+        if upload_file(audio_path.to_string_lossy().to_string())
+            .await
+            .is_ok()
+        {
+            manifest.audio_uploaded = true;
+        }
+    }
+
+    manifest.status = if manifest.video_uploaded && manifest.audio_uploaded {
+        SessionStatus::Finalized
+    } else {
+        SessionStatus::Stopped
+    };
+
+    write_manifest(&dir, &manifest);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("cap_recovery_test_{}_{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn fresh_manifest(session_id: &str) -> RecordingManifest {
+        RecordingManifest {
+            session_id: session_id.to_string(),
+            status: SessionStatus::InProgress,
+            video_uploaded: false,
+            audio_uploaded: false,
+        }
+    }
+
+    #[test]
+    fn write_then_read_round_trips() {
+        let dir = scratch_dir("round_trip");
+        write_manifest(&dir, &fresh_manifest("session-a"));
+
+        let read_back = read_manifest(&dir).unwrap();
+        assert_eq!(read_back.session_id, "session-a");
+        assert_eq!(read_back.status, SessionStatus::InProgress);
+    }
+
+    #[test]
+    fn mark_uploaded_finalizes_once_both_sides_land() {
+        let dir = scratch_dir("finalize");
+        write_manifest(&dir, &fresh_manifest("session-b"));
+
+        mark_video_uploaded(&dir);
+        let after_video = read_manifest(&dir).unwrap();
+        assert!(after_video.video_uploaded);
+        assert_eq!(after_video.status, SessionStatus::InProgress);
+
+        mark_audio_uploaded(&dir);
+        let after_audio = read_manifest(&dir).unwrap();
+        assert!(after_audio.audio_uploaded);
+        assert_eq!(after_audio.status, SessionStatus::Finalized);
+    }
+
+    #[test]
+    fn mark_stopped_sets_status() {
+        let dir = scratch_dir("stopped");
+        write_manifest(&dir, &fresh_manifest("session-c"));
+
+        mark_stopped(&dir);
+
+        assert_eq!(read_manifest(&dir).unwrap().status, SessionStatus::Stopped);
+    }
+
+    #[test]
+    fn video_segments_finds_only_segment_files() {
+        let dir = scratch_dir("segments");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("video_0.mp4"), b"a").unwrap();
+        fs::write(dir.join("video_1.mp4"), b"b").unwrap();
+        fs::write(dir.join("audio.mp3"), b"c").unwrap();
+
+        let mut found = video_segments(&dir);
+        found.sort();
+
+        assert_eq!(
+            found,
+            vec![dir.join("video_0.mp4"), dir.join("video_1.mp4")]
+        );
+    }
+}
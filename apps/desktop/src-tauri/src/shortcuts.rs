@@ -0,0 +1,116 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex as StdMutex};
+
+use tauri::{command, AppHandle, GlobalShortcutManager, Manager, State};
+use tokio::sync::Mutex;
+
+use crate::recording::{start_dual_recording, stop_all_recordings, RecordingState};
+
+const DEFAULT_ACCELERATOR: &str = "CmdOrCtrl+Shift+2";
+const SHORTCUT_FILE_NAME: &str = "shortcut.txt";
+
+pub struct ShortcutState {
+    pub accelerator: StdMutex<Option<String>>,
+    pub data_dir: Option<PathBuf>,
+}
+
+impl ShortcutState {
+    pub fn new(data_dir: Option<PathBuf>) -> Self {
+        Self {
+            accelerator: StdMutex::new(None),
+            data_dir,
+        }
+    }
+}
+
+fn shortcut_file(data_dir: &Option<PathBuf>) -> Option<PathBuf> {
+    data_dir.as_ref().map(|dir| dir.join(SHORTCUT_FILE_NAME))
+}
+
+fn load_persisted_accelerator(data_dir: &Option<PathBuf>) -> String {
+    shortcut_file(data_dir)
+        .and_then(|path| fs::read_to_string(path).ok())
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| DEFAULT_ACCELERATOR.to_string())
+}
+
+fn persist_accelerator(data_dir: &Option<PathBuf>, accelerator: &str) {
+    if let Some(path) = shortcut_file(data_dir) {
+        let _ = fs::write(path, accelerator);
+    }
+}
+
+async fn toggle_recording(app: &AppHandle) {
+    let state: State<'_, Arc<Mutex<RecordingState>>> = app.state();
+    let is_recording = state.lock().await.driver.is_some();
+
+    let result = if is_recording {
+        stop_all_recordings(state.clone()).await
+    } else {
+        start_dual_recording(state.clone(), app.clone(), None).await
+    };
+
+    if result.is_ok() {
+        let _ = app.emit_all("recording-toggled", !is_recording);
+    } else {
+        let _ = app.emit_all("recording-toggle-failed", ());
+    }
+}
+
+/// Registers `accelerator` as the global hotkey that toggles recording, replacing any
+/// previously registered binding, and persists it so it survives restarts.
+pub fn bind_shortcut(app: &AppHandle, accelerator: &str) -> Result<(), String> {
+    let shortcut_state: State<'_, ShortcutState> = app.state();
+
+    app.global_shortcut_manager()
+        .unregister_all()
+        .map_err(|e| e.to_string())?;
+
+    let app_handle = app.clone();
+    app.global_shortcut_manager()
+        .register(accelerator, move || {
+            let app_handle = app_handle.clone();
+            tauri::async_runtime::spawn(async move {
+                toggle_recording(&app_handle).await;
+            });
+        })
+        .map_err(|e| e.to_string())?;
+
+    persist_accelerator(&shortcut_state.data_dir, accelerator);
+    *shortcut_state.accelerator.lock().unwrap() = Some(accelerator.to_string());
+
+    let _ = app.emit_all("shortcut-changed", accelerator);
+
+    Ok(())
+}
+
+pub fn register_default_shortcut(app: &AppHandle, data_dir: &Option<PathBuf>) {
+    let accelerator = load_persisted_accelerator(data_dir);
+    if let Err(err) = bind_shortcut(app, &accelerator) {
+        eprintln!(
+            "Failed to register global shortcut '{}': {}",
+            accelerator, err
+        );
+    }
+}
+
+#[command]
+pub fn register_shortcut(app: AppHandle, accelerator: String) -> Result<(), String> {
+    bind_shortcut(&app, &accelerator)
+}
+
+#[command]
+pub fn unregister_shortcut(
+    app: AppHandle,
+    shortcut_state: State<'_, ShortcutState>,
+) -> Result<(), String> {
+    app.global_shortcut_manager()
+        .unregister_all()
+        .map_err(|e| e.to_string())?;
+
+    *shortcut_state.accelerator.lock().unwrap() = None;
+
+    Ok(())
+}
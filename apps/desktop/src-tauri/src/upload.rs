@@ -0,0 +1,27 @@
+use std::path::Path;
+
+use tauri::command;
+
+#[command]
+pub async fn upload_file(file_path: String) -> Result<String, String> {
+    let path = Path::new(&file_path);
+
+    if !path.exists() {
+        return Err(format!("File not found: {}", file_path));
+    }
+
+    let bytes = tokio::fs::read(path).await.map_err(|e| e.to_string())?;
+
+    let client = reqwest::Client::new();
+    let response = client
+        .post("https://api.cap.so/upload")
+        .body(bytes)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    response
+        .text()
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,109 @@
+use serde::{Deserialize, Serialize};
+use tauri::command;
+
+#[tauri::command]
+pub fn has_screen_capture_access() -> bool {
+    #[cfg(target_os = "macos")]
+    {
+        core_graphics_access::preflight_screen_capture_access()
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        true
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MediaAccessStatus {
+    NotDetermined,
+    Denied,
+    Restricted,
+    Granted,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MediaType {
+    Screen,
+    Microphone,
+    Camera,
+}
+
+/// Thin wrappers around the macOS privacy APIs so the command layer below doesn't
+/// have to deal with Objective-C bridging directly.
+#[cfg(target_os = "macos")]
+mod core_graphics_access {
+    use super::MediaAccessStatus;
+
+    // `CGPreflightScreenCaptureAccess` from CoreGraphics.
+    extern "C" {
+        fn CGPreflightScreenCaptureAccess() -> bool;
+    }
+
+    pub fn preflight_screen_capture_access() -> bool {
+        unsafe { CGPreflightScreenCaptureAccess() }
+    }
+
+    /// `CGPreflightScreenCaptureAccess` only returns a bool, so unlike
+    /// `av_authorization_status` below this can't tell a user who was never prompted
+    /// apart from one who explicitly denied access — both collapse to
+    /// `NotDetermined`. There's no public macOS API that distinguishes them for screen
+    /// recording, so this is a known limitation rather than a full 4-state mapping.
+    pub fn screen_status() -> MediaAccessStatus {
+        if preflight_screen_capture_access() {
+            MediaAccessStatus::Granted
+        } else {
+            MediaAccessStatus::NotDetermined
+        }
+    }
+
+    // `[AVCaptureDevice authorizationStatusForMediaType:]` returns an `NSInteger`
+    // in the range 0 (not determined) ..= 3 (authorized); see `AVAuthorizationStatus`.
+    // `av_media_type` is one of AVFoundation's `AVMediaTypeAudio`/`AVMediaTypeVideo`
+    // four-character codes ("soun"/"vide").
+    pub fn av_authorization_status(av_media_type: &str) -> MediaAccessStatus {
+        let status: i64 = cap_objc_bridge::authorization_status_for_media_type(av_media_type);
+        match status {
+            0 => MediaAccessStatus::NotDetermined,
+            1 => MediaAccessStatus::Restricted,
+            2 => MediaAccessStatus::Denied,
+            _ => MediaAccessStatus::Granted,
+        }
+    }
+
+    mod cap_objc_bridge {
+        use cocoa::base::nil;
+        use cocoa::foundation::NSString;
+        use objc::{class, msg_send, sel, sel_impl};
+
+        // Calls `[AVCaptureDevice authorizationStatusForMediaType:]` via the `objc`
+        // crate; kept behind this module boundary so callers deal in plain integers.
+        pub fn authorization_status_for_media_type(av_media_type: &str) -> i64 {
+            unsafe {
+                let media_type: cocoa::base::id = NSString::alloc(nil).init_str(av_media_type);
+                let status: i64 = msg_send![class!(AVCaptureDevice), authorizationStatusForMediaType: media_type];
+                status
+            }
+        }
+    }
+}
+
+#[command]
+pub fn get_media_access_status(media_type: MediaType) -> MediaAccessStatus {
+    #[cfg(target_os = "macos")]
+    {
+        match media_type {
+            MediaType::Screen => core_graphics_access::screen_status(),
+            MediaType::Microphone => core_graphics_access::av_authorization_status("soun"),
+            MediaType::Camera => core_graphics_access::av_authorization_status("vide"),
+        }
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = media_type;
+        MediaAccessStatus::Granted
+    }
+}